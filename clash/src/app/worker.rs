@@ -0,0 +1,125 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// A small worker-manager abstraction (in the spirit of garage's
+/// background worker framework) for the ad-hoc `tokio::spawn` calls that
+/// used to litter [`crate::app::nat_manager`]: every task spawned through
+/// here is named and tracked, can be told to wind down cleanly instead of
+/// being killed outright, and a task that dies is logged rather than
+/// disappearing without a trace.
+pub struct WorkerManager {
+    shutdown: ShutdownSignal,
+    handles: Mutex<Vec<(String, JoinHandle<()>)>>,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        Self {
+            shutdown: ShutdownSignal::new(),
+            handles: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// A handle callers thread into their own task bodies so they can
+    /// `subscribe()` and select against the receiver in their main loop.
+    pub fn shutdown_signal(&self) -> ShutdownSignal {
+        self.shutdown.clone()
+    }
+
+    /// Spawns and registers `fut` under `name`. The caller is responsible
+    /// for having `fut` observe a [`ShutdownSignal`] it subscribed to
+    /// beforehand; this only tracks the resulting handle so it can be
+    /// awaited (and its failure logged) from [`WorkerManager::shutdown`].
+    pub fn spawn<F>(&self, name: impl Into<String>, fut: F)
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let name = name.into();
+        let handle = tokio::spawn(fut);
+        self.handles
+            .lock()
+            .expect("worker registry poisoned")
+            .push((name, handle));
+    }
+
+    /// Broadcasts shutdown to every worker and waits for them all to
+    /// finish. A worker that panicked is logged and otherwise ignored -
+    /// shutdown still has to make progress for everyone else.
+    ///
+    /// `spawn` can race this call (e.g. a new NAT session being created
+    /// just as the proxy tears down), landing a handle in the registry
+    /// after the initial snapshot is taken. Rather than await a single
+    /// snapshot, keep draining the registry until nothing new shows up -
+    /// the latched [`ShutdownSignal`] guarantees any worker spawned after
+    /// `notify()` sees shutdown immediately, so this converges quickly.
+    pub async fn shutdown(&self) {
+        self.shutdown.notify();
+
+        loop {
+            let handles = {
+                let mut guard = self.handles.lock().expect("worker registry poisoned");
+                if guard.is_empty() {
+                    break;
+                }
+                std::mem::take(&mut *guard)
+            };
+            for (name, handle) in handles {
+                if let Err(err) = handle.await {
+                    tracing::warn!("background worker \"{name}\" exited abnormally: {err}");
+                }
+            }
+        }
+    }
+}
+
+impl Default for WorkerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cloneable handle to a manager's shutdown broadcast. Every worker
+/// subscribes once at spawn time and selects its `recv()` against its
+/// own loop.
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    tx: broadcast::Sender<()>,
+    // Latches "shutdown already happened" so a subscriber that arrives
+    // after `notify()` (a session spawned while the manager is tearing
+    // down) doesn't miss it - a broadcast channel never replays a message
+    // to a receiver that subscribes after `send` already fired.
+    fired: Arc<AtomicBool>,
+}
+
+impl ShutdownSignal {
+    fn new() -> Self {
+        // capacity 1 is enough: this only ever carries a single
+        // wake-everyone-up event, never a backlog of them.
+        let (tx, _rx) = broadcast::channel(1);
+        Self {
+            tx,
+            fired: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<()> {
+        let rx = self.tx.subscribe();
+        // Shutdown already fired before we subscribed: re-send so this
+        // (and any other late) receiver still observes it on its first
+        // `recv()` instead of waiting forever for an event that already
+        // happened.
+        if self.fired.load(Ordering::Acquire) {
+            let _ = self.tx.send(());
+        }
+        rx
+    }
+
+    fn notify(&self) {
+        self.fired.store(true, Ordering::Release);
+        // no subscribers is a legitimate state (e.g. nothing spawned yet).
+        let _ = self.tx.send(());
+    }
+}
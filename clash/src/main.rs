@@ -89,6 +89,8 @@ fn main() {
         }
     }
 
+    spawn_config_validate_supervisor(file.clone());
+
     match clash::start(clash::Options {
         config: clash::Config::File(file),
         cwd: cli.directory.map(|x| x.to_string_lossy().to_string()),
@@ -101,3 +103,66 @@ fn main() {
         }
     }
 }
+
+/// Watches for `SIGHUP` on a dedicated thread/runtime of its own - `start`
+/// above owns the main `TokioRuntime` and blocks the calling thread for
+/// the life of the process, so the supervisor can't just be another task
+/// on it.
+///
+/// Deliberately reduced scope: on every `SIGHUP` the config file is
+/// re-parsed and validated with the exact same `try_parse` used by
+/// `--test-config`, and nothing more. The full ask - atomically swapping
+/// the running rule/outbound set on a valid reload, so operators never
+/// have to restart - needs a hook into the running `clash_lib` instance
+/// (something like `clash::reload(validated_config)`) that doesn't exist
+/// in this tree. Until that hook lands, this only tells operators whether
+/// their edit *would* have been accepted, and a bad edit is logged and
+/// otherwise ignored so the last good config keeps serving. Do not read
+/// this as the hot-reload feature landing; it's the validate-only half of
+/// it.
+fn spawn_config_validate_supervisor(file: String) {
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start config-reload supervisor runtime");
+
+        rt.block_on(async move {
+            let mut hangup =
+                match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                    Ok(sig) => sig,
+                    Err(e) => {
+                        eprintln!(
+                            "failed to install SIGHUP handler, config validation on reload disabled: {}",
+                            e
+                        );
+                        return;
+                    }
+                };
+
+            loop {
+                hangup.recv().await;
+
+                match clash::Config::File(file.clone()).try_parse() {
+                    Ok(_) => {
+                        // Not a lie we can afford: nothing below this point
+                        // actually swaps the running rule/outbound set (see
+                        // the doc comment above), so don't tell the operator their
+                        // edit took effect when all that happened is a
+                        // syntax check.
+                        println!(
+                            "configuration file {} is valid (live reload not yet wired up)",
+                            file
+                        );
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "configuration file {} is invalid, keeping previous config: {}",
+                            file, e
+                        );
+                    }
+                }
+            }
+        });
+    });
+}